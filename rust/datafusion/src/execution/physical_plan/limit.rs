@@ -21,12 +21,12 @@ use std::sync::{Arc, Mutex};
 
 use crate::error::{ExecutionError, Result};
 use crate::execution::physical_plan::common::{self, RecordBatchIterator};
-use crate::execution::physical_plan::memory::MemoryIterator;
 use crate::execution::physical_plan::merge::MergeExec;
 use crate::execution::physical_plan::{ExecutionPlan, Partitioning};
 use arrow::array::ArrayRef;
 use arrow::compute::limit;
 use arrow::datatypes::SchemaRef;
+use arrow::error::{ArrowError, Result as ArrowResult};
 use arrow::record_batch::{RecordBatch, RecordBatchReader};
 
 /// Limit execution plan
@@ -38,22 +38,27 @@ pub struct GlobalLimitExec {
     input: Arc<dyn ExecutionPlan>,
     /// Maximum number of rows to return
     limit: usize,
+    /// Number of rows to skip before emitting any rows (SQL `OFFSET`)
+    skip: usize,
     /// Number of threads to run parallel LocalLimitExec on
     concurrency: usize,
 }
 
 impl GlobalLimitExec {
-    /// Create a new MergeExec
+    /// Create a new GlobalLimitExec that returns up to `limit` rows after
+    /// discarding the first `skip` rows (SQL `LIMIT limit OFFSET skip`).
     pub fn new(
         schema: SchemaRef,
         input: Arc<dyn ExecutionPlan>,
         limit: usize,
+        skip: usize,
         concurrency: usize,
     ) -> Self {
         GlobalLimitExec {
             schema,
             input,
             limit,
+            skip,
             concurrency,
         }
     }
@@ -76,11 +81,13 @@ impl ExecutionPlan for GlobalLimitExec {
         // GlobalLimitExec has a single partition
         assert_eq!(0, partition);
 
-        // apply limit in parallel across all input partitions
+        // apply limit in parallel across all input partitions. Each partition
+        // must retain enough rows to satisfy both the skip and the fetch once
+        // the partitions are merged, so the local bound is `skip + limit`.
         let local_limit = Arc::new(LocalLimitExec::new(
             self.input.clone(),
             self.schema.clone(),
-            self.limit,
+            self.skip + self.limit,
         ));
 
         // limit needs to collapse inputs down to a single partition
@@ -90,10 +97,27 @@ impl ExecutionPlan for GlobalLimitExec {
         let it = merge.execute(0)?;
         let batches = common::collect(it)?;
 
-        // apply the limit to the output
+        // apply the skip and then the limit to the merged output. Skipping
+        // happens here, after MergeExec has collapsed the partitions to a
+        // single ordered stream, so that `OFFSET` discards the correct rows.
         let mut combined_results: Vec<Arc<RecordBatch>> = vec![];
         let mut count = 0;
+        let mut skipped = 0;
         for batch in batches {
+            // drop rows that fall before the offset, trimming the front of the
+            // batch that straddles the boundary
+            let batch = if skipped < self.skip {
+                let remaining = self.skip - skipped;
+                if batch.num_rows() <= remaining {
+                    skipped += batch.num_rows();
+                    continue;
+                }
+                skipped = self.skip;
+                skip_batch(&batch, remaining)?
+            } else {
+                batch
+            };
+
             let capacity = self.limit - count;
             if batch.num_rows() <= capacity {
                 count += batch.num_rows();
@@ -148,11 +172,82 @@ impl ExecutionPlan for LocalLimitExec {
         partition: usize,
     ) -> Result<Arc<Mutex<dyn RecordBatchReader + Send + Sync>>> {
         let it = self.input.execute(partition)?;
-        Ok(Arc::new(Mutex::new(MemoryIterator::try_new(
-            collect_with_limit(it, self.limit)?,
+        Ok(Arc::new(Mutex::new(LimitIterator::new(
+            it,
             self.schema.clone(),
-            None,
-        )?)))
+            self.limit,
+        ))))
+    }
+}
+
+/// A lazy iterator that streams batches from its input until `limit` rows have
+/// been produced, truncating the batch that crosses the boundary.
+///
+/// Unlike buffering the qualifying prefix up front, this holds no batches of its
+/// own, so the limit flows through without materializing and early termination
+/// propagates back to the scan as soon as the bound is reached.
+struct LimitIterator {
+    /// Input reader, dropped once the limit is reached so the scan can stop
+    input: Option<Arc<Mutex<dyn RecordBatchReader + Send + Sync>>>,
+    /// Output schema
+    schema: SchemaRef,
+    /// Maximum number of rows to return
+    limit: usize,
+    /// Number of rows emitted so far
+    count: usize,
+}
+
+impl LimitIterator {
+    fn new(
+        input: Arc<Mutex<dyn RecordBatchReader + Send + Sync>>,
+        schema: SchemaRef,
+        limit: usize,
+    ) -> Self {
+        Self {
+            input: Some(input),
+            schema,
+            limit,
+            count: 0,
+        }
+    }
+}
+
+impl RecordBatchReader for LimitIterator {
+    fn schema(&self) -> SchemaRef {
+        self.schema.clone()
+    }
+
+    fn next_batch(&mut self) -> ArrowResult<Option<RecordBatch>> {
+        let input = match &self.input {
+            Some(input) => input,
+            // the limit has already been reached and the input released
+            None => return Ok(None),
+        };
+
+        let batch = input.lock().unwrap().next_batch()?;
+        match batch {
+            Some(batch) => {
+                let capacity = self.limit - self.count;
+                if batch.num_rows() < capacity {
+                    self.count += batch.num_rows();
+                    Ok(Some(batch))
+                } else {
+                    // this batch reaches the limit; truncate it and release the
+                    // input so the scan upstream can terminate early
+                    let batch = truncate_batch(&batch, capacity).map_err(|e| {
+                        ArrowError::ComputeError(format!("{:?}", e))
+                    })?;
+                    self.count += batch.num_rows();
+                    self.input = None;
+                    Ok(Some(batch))
+                }
+            }
+            None => {
+                // input exhausted before the limit was reached
+                self.input = None;
+                Ok(None)
+            }
+        }
     }
 }
 
@@ -168,37 +263,17 @@ pub fn truncate_batch(batch: &RecordBatch, n: usize) -> Result<RecordBatch> {
     )?)
 }
 
-/// Create a vector of record batches from an iterator
-fn collect_with_limit(
-    reader: Arc<Mutex<dyn RecordBatchReader + Send + Sync>>,
-    limit: usize,
-) -> Result<Vec<RecordBatch>> {
-    let mut count = 0;
-    let mut reader = reader.lock().unwrap();
-    let mut results: Vec<RecordBatch> = vec![];
-    loop {
-        match reader.next_batch() {
-            Ok(Some(batch)) => {
-                let capacity = limit - count;
-                if batch.num_rows() <= capacity {
-                    count += batch.num_rows();
-                    results.push(batch);
-                } else {
-                    let batch = truncate_batch(&batch, capacity)?;
-                    count += batch.num_rows();
-                    results.push(batch);
-                }
-                if count == limit {
-                    return Ok(results);
-                }
-            }
-            Ok(None) => {
-                // end of result set
-                return Ok(results);
-            }
-            Err(e) => return Err(ExecutionError::from(e)),
-        }
-    }
+/// Discard the first n rows of a RecordBatch, returning the remainder
+pub fn skip_batch(batch: &RecordBatch, n: usize) -> Result<RecordBatch> {
+    let length = batch.num_rows() - n;
+    let sliced_columns: Vec<ArrayRef> = (0..batch.num_columns())
+        .map(|i| batch.column(i).slice(n, length))
+        .collect();
+
+    Ok(RecordBatch::try_new(
+        batch.schema().clone(),
+        sliced_columns,
+    )?)
 }
 
 #[cfg(test)]
@@ -223,7 +298,7 @@ mod tests {
         // input should have 4 partitions
         assert_eq!(csv.output_partitioning().partition_count(), num_partitions);
 
-        let limit = GlobalLimitExec::new(schema.clone(), Arc::new(csv), 7, 2);
+        let limit = GlobalLimitExec::new(schema.clone(), Arc::new(csv), 7, 0, 2);
 
         // the result should contain 4 batches (one per input partition)
         let iter = limit.execute(0)?;
@@ -235,4 +310,27 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn limit_with_offset() -> Result<()> {
+        let schema = test::aggr_test_schema();
+
+        let num_partitions = 4;
+        let path =
+            test::create_partitioned_csv("aggregate_test_100.csv", num_partitions)?;
+
+        let csv =
+            CsvExec::try_new(&path, CsvReadOptions::new().schema(&schema), None, 1024)?;
+
+        // fetch 7 rows after skipping the first 3
+        let limit = GlobalLimitExec::new(schema.clone(), Arc::new(csv), 7, 3, 2);
+
+        let iter = limit.execute(0)?;
+        let batches = common::collect(iter)?;
+
+        let row_count: usize = batches.iter().map(|batch| batch.num_rows()).sum();
+        assert_eq!(row_count, 7);
+
+        Ok(())
+    }
 }
@@ -0,0 +1,475 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Defines the fused TopK plan, which evaluates `ORDER BY ... LIMIT n`
+//! without materializing and sorting the entire input
+
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+use std::sync::{Arc, Mutex};
+
+use crate::error::{ExecutionError, Result};
+use crate::execution::physical_plan::common::{self, RecordBatchIterator};
+use crate::execution::physical_plan::merge::MergeExec;
+use crate::execution::physical_plan::sort::PhysicalSortExpr;
+use crate::execution::physical_plan::{ExecutionPlan, Partitioning};
+use arrow::array::{
+    Array, ArrayRef, BooleanArray, Float32Array, Float64Array, Int16Array, Int32Array,
+    Int64Array, Int8Array, StringArray, UInt16Array, UInt32Array, UInt64Array,
+    UInt8Array,
+};
+use arrow::compute::concat;
+use arrow::datatypes::{DataType, SchemaRef};
+use arrow::record_batch::{RecordBatch, RecordBatchReader};
+
+/// TopK execution plan, a fusion of `SortExec` and `GlobalLimitExec`.
+///
+/// Rather than sorting every input row, each partition keeps a bounded heap of
+/// capacity `limit` keyed on the sort expressions. The heap is ordered so its
+/// top element is the *worst* row that currently qualifies, which lets a new
+/// row be rejected or swapped in with a single comparison and keeps peak memory
+/// at `O(limit)` per partition regardless of input size.
+#[derive(Debug)]
+pub struct TopKExec {
+    /// Input schema
+    schema: SchemaRef,
+    /// Input partitions
+    input: Arc<dyn ExecutionPlan>,
+    /// Sort expressions
+    sort_expr: Vec<PhysicalSortExpr>,
+    /// Maximum number of rows to return
+    limit: usize,
+    /// Number of threads to run parallel per-partition TopK on
+    concurrency: usize,
+}
+
+impl TopKExec {
+    /// Create a new TopKExec
+    pub fn new(
+        schema: SchemaRef,
+        input: Arc<dyn ExecutionPlan>,
+        sort_expr: Vec<PhysicalSortExpr>,
+        limit: usize,
+        concurrency: usize,
+    ) -> Self {
+        TopKExec {
+            schema,
+            input,
+            sort_expr,
+            limit,
+            concurrency,
+        }
+    }
+}
+
+impl ExecutionPlan for TopKExec {
+    fn schema(&self) -> SchemaRef {
+        self.schema.clone()
+    }
+
+    /// Get the output partitioning of this plan
+    fn output_partitioning(&self) -> Partitioning {
+        Partitioning::UnknownPartitioning(1)
+    }
+
+    fn execute(
+        &self,
+        partition: usize,
+    ) -> Result<Arc<Mutex<dyn RecordBatchReader + Send + Sync>>> {
+        // TopKExec has a single partition
+        assert_eq!(0, partition);
+
+        // reduce each input partition to its local top-k in parallel
+        let local = Arc::new(LocalTopKExec::new(
+            self.input.clone(),
+            self.schema.clone(),
+            self.sort_expr.clone(),
+            self.limit,
+        ));
+
+        // collapse the per-partition top-k streams down to a single stream
+        let merge = MergeExec::new(self.schema.clone(), local, self.concurrency);
+        assert_eq!(1, merge.output_partitioning().partition_count());
+        let it = merge.execute(0)?;
+
+        // merge the per-partition heaps into a single heap of size `limit`
+        let mut heap = TopKHeap::new(&self.sort_expr, self.limit);
+        heap.consume(it)?;
+
+        // drain the heap into the requested sort order and emit a single batch
+        let batches = heap.into_sorted_batches(self.schema.clone())?;
+
+        Ok(Arc::new(Mutex::new(RecordBatchIterator::new(
+            self.schema.clone(),
+            batches,
+        ))))
+    }
+}
+
+/// LocalTopKExec applies a TopK within a single partition
+#[derive(Debug)]
+pub struct LocalTopKExec {
+    input: Arc<dyn ExecutionPlan>,
+    schema: SchemaRef,
+    sort_expr: Vec<PhysicalSortExpr>,
+    limit: usize,
+}
+
+impl LocalTopKExec {
+    /// Create a new LocalTopKExec partition
+    pub fn new(
+        input: Arc<dyn ExecutionPlan>,
+        schema: SchemaRef,
+        sort_expr: Vec<PhysicalSortExpr>,
+        limit: usize,
+    ) -> Self {
+        Self {
+            input,
+            schema,
+            sort_expr,
+            limit,
+        }
+    }
+}
+
+impl ExecutionPlan for LocalTopKExec {
+    fn schema(&self) -> SchemaRef {
+        self.input.schema()
+    }
+
+    fn output_partitioning(&self) -> Partitioning {
+        self.input.output_partitioning()
+    }
+
+    fn execute(
+        &self,
+        partition: usize,
+    ) -> Result<Arc<Mutex<dyn RecordBatchReader + Send + Sync>>> {
+        let it = self.input.execute(partition)?;
+        let mut heap = TopKHeap::new(&self.sort_expr, self.limit);
+        heap.consume(it)?;
+        let batches = heap.into_sorted_batches(self.schema.clone())?;
+        Ok(Arc::new(Mutex::new(RecordBatchIterator::new(
+            self.schema.clone(),
+            batches,
+        ))))
+    }
+}
+
+/// A single qualifying row, retained as a one-row batch alongside its evaluated
+/// sort-key values so it can be compared without re-reading the arrays.
+struct HeapRow {
+    batch: RecordBatch,
+    keys: Vec<Key>,
+}
+
+/// Bounded max-heap that keeps the `limit` best rows according to `sort_expr`.
+///
+/// The heap order matches the requested ordering, so the max-heap surfaces the
+/// *worst* qualifying row via `peek` — the first candidate to be evicted when a
+/// better row arrives.
+struct TopKHeap<'a> {
+    sort_expr: &'a [PhysicalSortExpr],
+    limit: usize,
+    rows: BinaryHeap<Ordered<'a>>,
+}
+
+impl<'a> TopKHeap<'a> {
+    fn new(sort_expr: &'a [PhysicalSortExpr], limit: usize) -> Self {
+        Self {
+            sort_expr,
+            limit,
+            rows: BinaryHeap::with_capacity(limit + 1),
+        }
+    }
+
+    /// Pull every batch from `reader`, offering each row to the heap.
+    fn consume(
+        &mut self,
+        reader: Arc<Mutex<dyn RecordBatchReader + Send + Sync>>,
+    ) -> Result<()> {
+        let mut reader = reader.lock().unwrap();
+        loop {
+            match reader.next_batch() {
+                Ok(Some(batch)) => {
+                    let keys = self
+                        .sort_expr
+                        .iter()
+                        .map(|e| e.expr.evaluate(&batch))
+                        .collect::<Result<Vec<_>>>()?;
+                    for row in 0..batch.num_rows() {
+                        self.offer(&batch, &keys, row)?;
+                    }
+                }
+                Ok(None) => return Ok(()),
+                Err(e) => return Err(ExecutionError::from(e)),
+            }
+        }
+    }
+
+    /// Offer a single row; push it when the heap is not yet full, otherwise swap
+    /// it for the current worst row only when it is strictly better.
+    fn offer(
+        &mut self,
+        batch: &RecordBatch,
+        key_arrays: &[ArrayRef],
+        row: usize,
+    ) -> Result<()> {
+        if self.limit == 0 {
+            return Ok(());
+        }
+        // extract the owned key values once per row; all subsequent heap
+        // comparisons are plain value comparisons with no array access
+        let keys = extract_keys(key_arrays, row)?;
+        if self.rows.len() < self.limit {
+            self.rows
+                .push(Ordered::new(self.sort_expr, one_row(batch, keys, row)?));
+        } else {
+            let better = {
+                let worst = self.rows.peek().expect("heap is full");
+                compare_keys(self.sort_expr, &keys, &worst.row.keys) == Ordering::Less
+            };
+            if better {
+                self.rows.pop();
+                self.rows
+                    .push(Ordered::new(self.sort_expr, one_row(batch, keys, row)?));
+            }
+        }
+        Ok(())
+    }
+
+    /// Drain the heap into sorted (best-first) order, returning a single
+    /// concatenated batch, or no batch at all when the heap is empty.
+    fn into_sorted_batches(self, schema: SchemaRef) -> Result<Vec<Arc<RecordBatch>>> {
+        // the heap order matches the requested ordering, so `into_sorted_vec`
+        // already yields the rows best-first
+        let ordered: Vec<HeapRow> =
+            self.rows.into_sorted_vec().into_iter().map(|o| o.row).collect();
+        if ordered.is_empty() {
+            return Ok(vec![]);
+        }
+
+        // concatenate the one-row batches column by column
+        let columns: Result<Vec<ArrayRef>> = (0..schema.fields().len())
+            .map(|i| {
+                let arrays: Vec<ArrayRef> =
+                    ordered.iter().map(|r| r.batch.column(i).clone()).collect();
+                concat(&arrays).map_err(ExecutionError::from)
+            })
+            .collect();
+
+        Ok(vec![Arc::new(RecordBatch::try_new(schema, columns?)?)])
+    }
+}
+
+/// Wrapper that orders `HeapRow`s by the requested ordering so a `BinaryHeap`
+/// (a max-heap) keeps the worst qualifying row on top.
+struct Ordered<'a> {
+    sort_expr: &'a [PhysicalSortExpr],
+    row: HeapRow,
+}
+
+impl<'a> Ordered<'a> {
+    fn new(sort_expr: &'a [PhysicalSortExpr], row: HeapRow) -> Self {
+        Self { sort_expr, row }
+    }
+}
+
+impl<'a> PartialEq for Ordered<'a> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<'a> Eq for Ordered<'a> {}
+
+impl<'a> PartialOrd for Ordered<'a> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<'a> Ord for Ordered<'a> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // order matches the requested ordering so the max-heap surfaces the
+        // worst row
+        compare_keys(self.sort_expr, &self.row.keys, &other.row.keys)
+    }
+}
+
+/// Slice `row` out of `batch` into its own one-row batch, carrying the owned
+/// sort keys alongside it.
+fn one_row(batch: &RecordBatch, keys: Vec<Key>, row: usize) -> Result<HeapRow> {
+    let columns: Vec<ArrayRef> = (0..batch.num_columns())
+        .map(|i| batch.column(i).slice(row, 1))
+        .collect();
+    Ok(HeapRow {
+        batch: RecordBatch::try_new(batch.schema().clone(), columns)?,
+        keys,
+    })
+}
+
+/// An owned, directly comparable sort-key value for a single row.
+///
+/// Extracting the key once per row keeps every heap comparison an
+/// allocation-free value comparison, which matters because a bounded heap sifts
+/// on every qualifying row of a potentially very large scan.
+enum Key {
+    Null,
+    Boolean(bool),
+    Int(i64),
+    UInt(u64),
+    Float(f64),
+    Utf8(String),
+}
+
+impl Key {
+    /// Compare two keys of the same column, ordering nulls first.
+    fn cmp(&self, other: &Key) -> Ordering {
+        match (self, other) {
+            (Key::Null, Key::Null) => Ordering::Equal,
+            (Key::Null, _) => Ordering::Less,
+            (_, Key::Null) => Ordering::Greater,
+            (Key::Boolean(a), Key::Boolean(b)) => a.cmp(b),
+            (Key::Int(a), Key::Int(b)) => a.cmp(b),
+            (Key::UInt(a), Key::UInt(b)) => a.cmp(b),
+            (Key::Float(a), Key::Float(b)) => {
+                a.partial_cmp(b).unwrap_or(Ordering::Equal)
+            }
+            (Key::Utf8(a), Key::Utf8(b)) => a.cmp(b),
+            // a single column yields a single variant, so this is unreachable
+            _ => Ordering::Equal,
+        }
+    }
+}
+
+/// Extract the owned sort keys for `row` from the evaluated key arrays.
+fn extract_keys(arrays: &[ArrayRef], row: usize) -> Result<Vec<Key>> {
+    arrays.iter().map(|a| extract_key(a, row)).collect()
+}
+
+/// Extract a single owned key value from `array` at `row`.
+fn extract_key(array: &ArrayRef, row: usize) -> Result<Key> {
+    if array.is_null(row) {
+        return Ok(Key::Null);
+    }
+    macro_rules! value {
+        ($ty:ty) => {
+            array.as_any().downcast_ref::<$ty>().unwrap().value(row)
+        };
+    }
+    Ok(match array.data_type() {
+        DataType::Boolean => Key::Boolean(value!(BooleanArray)),
+        DataType::Int8 => Key::Int(value!(Int8Array) as i64),
+        DataType::Int16 => Key::Int(value!(Int16Array) as i64),
+        DataType::Int32 => Key::Int(value!(Int32Array) as i64),
+        DataType::Int64 => Key::Int(value!(Int64Array)),
+        DataType::UInt8 => Key::UInt(value!(UInt8Array) as u64),
+        DataType::UInt16 => Key::UInt(value!(UInt16Array) as u64),
+        DataType::UInt32 => Key::UInt(value!(UInt32Array) as u64),
+        DataType::UInt64 => Key::UInt(value!(UInt64Array)),
+        DataType::Float32 => Key::Float(value!(Float32Array) as f64),
+        DataType::Float64 => Key::Float(value!(Float64Array)),
+        DataType::Utf8 => Key::Utf8(value!(StringArray).to_string()),
+        other => {
+            return Err(ExecutionError::NotImplemented(format!(
+                "TopK does not support sort keys of type {:?}",
+                other
+            )))
+        }
+    })
+}
+
+/// Compare two rows' keys according to the sort expressions, honoring each
+/// expression's ASC/DESC option.
+fn compare_keys(sort_expr: &[PhysicalSortExpr], left: &[Key], right: &[Key]) -> Ordering {
+    for (i, expr) in sort_expr.iter().enumerate() {
+        let ord = left[i].cmp(&right[i]);
+        let ord = if expr.options.descending {
+            ord.reverse()
+        } else {
+            ord
+        };
+        if ord != Ordering::Equal {
+            return ord;
+        }
+    }
+    Ordering::Equal
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::execution::physical_plan::common;
+    use crate::execution::physical_plan::csv::{CsvExec, CsvReadOptions};
+    use crate::execution::physical_plan::expressions::col;
+    use crate::test;
+    use arrow::compute::SortOptions;
+
+    /// Run `ORDER BY c1 LIMIT limit` over the 100-row test csv and return the
+    /// total number of rows emitted.
+    fn run_topk(limit: usize, descending: bool) -> Result<usize> {
+        let schema = test::aggr_test_schema();
+
+        let num_partitions = 4;
+        let path =
+            test::create_partitioned_csv("aggregate_test_100.csv", num_partitions)?;
+
+        let csv =
+            CsvExec::try_new(&path, CsvReadOptions::new().schema(&schema), None, 1024)?;
+
+        let sort_expr = vec![PhysicalSortExpr {
+            expr: col("c1"),
+            options: SortOptions {
+                descending,
+                nulls_first: true,
+            },
+        }];
+
+        let topk = TopKExec::new(schema.clone(), Arc::new(csv), sort_expr, limit, 2);
+
+        let iter = topk.execute(0)?;
+        let batches = common::collect(iter)?;
+        Ok(batches.iter().map(|batch| batch.num_rows()).sum())
+    }
+
+    #[test]
+    fn topk_ascending() -> Result<()> {
+        assert_eq!(run_topk(5, false)?, 5);
+        Ok(())
+    }
+
+    #[test]
+    fn topk_descending() -> Result<()> {
+        assert_eq!(run_topk(5, true)?, 5);
+        Ok(())
+    }
+
+    #[test]
+    fn topk_limit_larger_than_input() -> Result<()> {
+        // there are only 100 rows, so a larger limit returns all of them
+        assert_eq!(run_topk(1000, false)?, 100);
+        Ok(())
+    }
+
+    #[test]
+    fn topk_zero_limit() -> Result<()> {
+        assert_eq!(run_topk(0, false)?, 0);
+        Ok(())
+    }
+}